@@ -1,45 +1,65 @@
-use std::{fmt::Debug, hash::Hash};
+use std::fmt::Debug;
+
+use num_traits::{Bounded, One, SaturatingAdd, SaturatingSub, WrappingAdd, Zero};
 
 /// This trait defines an auto-increment integer.
 ///
-/// However, you can implement it for your own types.
-pub trait Aictable: Clone + Debug + Eq + PartialEq + Hash {
+/// It is blanket-implemented for any type satisfying [`Ord`], [`Zero`], [`One`],
+/// [`Bounded`], [`WrappingAdd`], [`SaturatingAdd`] and [`SaturatingSub`] from the
+/// [`num-traits`](https://docs.rs/num-traits) crate, so all the built-in integer
+/// types (`i8`, `i16`, `i32`, `i64`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize`)
+/// get this trait for free, along with any conforming custom type.
+pub trait Aictable:
+    Clone + Debug + Eq + Ord + Bounded + One + SaturatingAdd + SaturatingSub + WrappingAdd + Zero
+{
     /// The initial value for the type implementing this trait.
-    const INITIAL: Self;
+    fn initial() -> Self {
+        Self::zero()
+    }
 
     /// Checks if the maximum value for the type has been reached.
-    fn is_max_reached(&self) -> bool;
+    fn is_max_reached(&self) -> bool {
+        *self == Self::max_value()
+    }
 
-    /// Returns the next value, saturating at the numeric bounds instead of overflowing.
-    fn saturating_next(&self) -> Self;
+    /// Returns the value obtained by advancing by `step`, saturating at the
+    /// numeric bounds instead of overflowing.
+    fn saturating_add_step(&self, step: &Self) -> Self {
+        SaturatingAdd::saturating_add(self, step)
+    }
 
-    /// Returns the next value, wrapping around at the numeric bounds.
-    fn wrapping_next(&self) -> Self;
-}
+    /// Returns the value obtained by advancing by `step`, wrapping around at
+    /// the numeric bounds.
+    fn wrapping_add_step(&self, step: &Self) -> Self {
+        WrappingAdd::wrapping_add(self, step)
+    }
+
+    /// Returns the value immediately after this one, saturating at the maximum
+    /// value of the type instead of overflowing.
+    ///
+    /// Used by the free-interval bookkeeping in [`Factory`](crate::Factory) to
+    /// tell whether two intervals abut.
+    fn succ(&self) -> Self {
+        self.saturating_add_step(&Self::one())
+    }
+
+    /// Returns the value immediately before this one, saturating at the minimum
+    /// value of the type instead of underflowing.
+    fn pred(&self) -> Self {
+        self.saturating_sub_value(&Self::one())
+    }
 
-macro_rules! impl_aictable {
-    ($($t:ty),*) => {
-        $(
-            impl Aictable for $t {
-                const INITIAL: Self = Self::MIN;
-
-                fn is_max_reached(&self) -> bool {
-                    *self == Self::MAX
-                }
-
-                fn saturating_next(&self) -> Self {
-                    self.saturating_add(1)
-                }
-
-                fn wrapping_next(&self) -> Self {
-                    self.wrapping_add(1)
-                }
-            }
-        )*
-    };
+    /// Returns the value obtained by subtracting `other`, saturating at the
+    /// minimum value of the type instead of underflowing.
+    fn saturating_sub_value(&self, other: &Self) -> Self {
+        SaturatingSub::saturating_sub(self, other)
+    }
 }
 
-impl_aictable!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl<T> Aictable for T where
+    T: Clone + Debug + Eq + Ord + Bounded + One + SaturatingAdd + SaturatingSub + WrappingAdd + Zero
+{
+}
 
 #[cfg(test)]
 mod tests {
@@ -47,121 +67,140 @@ mod tests {
 
     #[test]
     fn test_aictable_i8() {
-        let mut num = i8::INITIAL;
-        assert_eq!(num, i8::MIN);
+        let mut num = i8::initial();
+        assert_eq!(num, 0);
         assert!(!num.is_max_reached());
 
         num = i8::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), i8::MAX);
-        assert_eq!(num.wrapping_next(), i8::MIN);
+        assert_eq!(num.saturating_add_step(&1), i8::MAX);
+        assert_eq!(num.wrapping_add_step(&1), i8::MIN);
     }
 
     #[test]
     fn test_aictable_i16() {
-        let mut num = i16::INITIAL;
-        assert_eq!(num, i16::MIN);
+        let mut num = i16::initial();
+        assert_eq!(num, 0);
         assert!(!num.is_max_reached());
 
         num = i16::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), i16::MAX);
-        assert_eq!(num.wrapping_next(), i16::MIN);
+        assert_eq!(num.saturating_add_step(&1), i16::MAX);
+        assert_eq!(num.wrapping_add_step(&1), i16::MIN);
     }
 
     #[test]
     fn test_aictable_i32() {
-        let mut num = i32::INITIAL;
-        assert_eq!(num, i32::MIN);
+        let mut num = i32::initial();
+        assert_eq!(num, 0);
         assert!(!num.is_max_reached());
 
         num = i32::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), i32::MAX);
-        assert_eq!(num.wrapping_next(), i32::MIN);
+        assert_eq!(num.saturating_add_step(&1), i32::MAX);
+        assert_eq!(num.wrapping_add_step(&1), i32::MIN);
     }
 
     #[test]
     fn test_aictable_i64() {
-        let mut num = i64::INITIAL;
-        assert_eq!(num, i64::MIN);
+        let mut num = i64::initial();
+        assert_eq!(num, 0);
         assert!(!num.is_max_reached());
 
         num = i64::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), i64::MAX);
-        assert_eq!(num.wrapping_next(), i64::MIN);
+        assert_eq!(num.saturating_add_step(&1), i64::MAX);
+        assert_eq!(num.wrapping_add_step(&1), i64::MIN);
     }
 
     #[test]
     fn test_aictable_isize() {
-        let mut num = isize::INITIAL;
-        assert_eq!(num, isize::MIN);
+        let mut num = isize::initial();
+        assert_eq!(num, 0);
         assert!(!num.is_max_reached());
 
         num = isize::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), isize::MAX);
-        assert_eq!(num.wrapping_next(), isize::MIN);
+        assert_eq!(num.saturating_add_step(&1), isize::MAX);
+        assert_eq!(num.wrapping_add_step(&1), isize::MIN);
     }
 
     #[test]
     fn test_aictable_u8() {
-        let mut num = u8::INITIAL;
+        let mut num = u8::initial();
         assert_eq!(num, u8::MIN);
         assert!(!num.is_max_reached());
 
         num = u8::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), u8::MAX);
-        assert_eq!(num.wrapping_next(), u8::MIN);
+        assert_eq!(num.saturating_add_step(&1), u8::MAX);
+        assert_eq!(num.wrapping_add_step(&1), u8::MIN);
     }
 
     #[test]
     fn test_aictable_u16() {
-        let mut num = u16::INITIAL;
+        let mut num = u16::initial();
         assert_eq!(num, u16::MIN);
         assert!(!num.is_max_reached());
 
         num = u16::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), u16::MAX);
-        assert_eq!(num.wrapping_next(), u16::MIN);
+        assert_eq!(num.saturating_add_step(&1), u16::MAX);
+        assert_eq!(num.wrapping_add_step(&1), u16::MIN);
     }
 
     #[test]
     fn test_aictable_u32() {
-        let mut num = u32::INITIAL;
+        let mut num = u32::initial();
         assert_eq!(num, u32::MIN);
         assert!(!num.is_max_reached());
 
         num = u32::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), u32::MAX);
-        assert_eq!(num.wrapping_next(), u32::MIN);
+        assert_eq!(num.saturating_add_step(&1), u32::MAX);
+        assert_eq!(num.wrapping_add_step(&1), u32::MIN);
     }
 
     #[test]
     fn test_aictable_u64() {
-        let mut num = u64::INITIAL;
+        let mut num = u64::initial();
         assert_eq!(num, u64::MIN);
         assert!(!num.is_max_reached());
 
         num = u64::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), u64::MAX);
-        assert_eq!(num.wrapping_next(), u64::MIN);
+        assert_eq!(num.saturating_add_step(&1), u64::MAX);
+        assert_eq!(num.wrapping_add_step(&1), u64::MIN);
     }
 
     #[test]
     fn test_aictable_usize() {
-        let mut num = usize::INITIAL;
+        let mut num = usize::initial();
         assert_eq!(num, usize::MIN);
         assert!(!num.is_max_reached());
 
         num = usize::MAX;
         assert!(num.is_max_reached());
-        assert_eq!(num.saturating_next(), usize::MAX);
-        assert_eq!(num.wrapping_next(), usize::MIN);
+        assert_eq!(num.saturating_add_step(&1), usize::MAX);
+        assert_eq!(num.wrapping_add_step(&1), usize::MIN);
+    }
+
+    #[test]
+    fn test_aictable_step() {
+        let num = 0u32;
+
+        assert_eq!(num.saturating_add_step(&4), 4);
+        assert_eq!(num.wrapping_add_step(&4), 4);
+        assert_eq!(u32::MAX.saturating_add_step(&4), u32::MAX);
+        assert_eq!(u32::MAX.wrapping_add_step(&4), 3);
+    }
+
+    #[test]
+    fn test_aictable_succ_pred() {
+        assert_eq!(1u32.succ(), 2);
+        assert_eq!(u32::MAX.succ(), u32::MAX);
+
+        assert_eq!(1u32.pred(), 0);
+        assert_eq!(0u32.pred(), 0);
     }
 }
@@ -0,0 +1,143 @@
+//! Optional support for the fixed-width integer types from the
+//! [`arbitrary-int`](https://docs.rs/arbitrary-int) crate (`u4`, `u7`, `u24`, `u48`, ...),
+//! enabled via the `arbitrary-int` Cargo feature.
+//!
+//! These types don't implement the `num-traits` bounds that [`Aictable`](crate::Aictable)'s
+//! blanket impl requires, and even if they did, their off-the-shelf wrapping/saturating
+//! arithmetic would operate at the *underlying storage type*'s bound (e.g. `u24` is stored
+//! in a `u32`) rather than the true `2^BITS - 1` bound. [`ArbitraryIntId`] wraps one of
+//! these types and implements the required `num-traits` traits itself, respecting the
+//! real bit width, so it picks up [`Aictable`](crate::Aictable) for free through the
+//! blanket impl in `aictable.rs` instead of needing its own conflicting impl.
+
+use std::ops::{Add, Mul, Sub};
+
+use arbitrary_int::{u24, u4, u48, u7, Number};
+use num_traits::{Bounded, One, SaturatingAdd, SaturatingSub, WrappingAdd, Zero};
+
+/// Wraps a fixed-width integer type from `arbitrary-int` so it can be used as an
+/// [`Aictable`](crate::Aictable) ID type, e.g. `Factory::<ArbitraryIntId<u24>>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArbitraryIntId<T>(pub T);
+
+macro_rules! impl_num_traits_for_arbitrary_int {
+    ($($t:ty),*) => {
+        $(
+            // `Zero`/`One`/`Saturating*`/`WrappingAdd` each carry an `Add`/`Sub`/`Mul`
+            // supertrait bound; these stay within the true `2^BITS - 1` bound (`Add`
+            // and `Mul` wrap, `Sub` saturates) so the behavior lines up with the
+            // saturating/wrapping methods below.
+            impl Add for ArbitraryIntId<$t> {
+                type Output = Self;
+
+                fn add(self, other: Self) -> Self {
+                    self.wrapping_add(&other)
+                }
+            }
+
+            impl Sub for ArbitraryIntId<$t> {
+                type Output = Self;
+
+                fn sub(self, other: Self) -> Self {
+                    self.saturating_sub(&other)
+                }
+            }
+
+            impl Mul for ArbitraryIntId<$t> {
+                type Output = Self;
+
+                fn mul(self, other: Self) -> Self {
+                    let width = u128::from(<$t>::MAX.value()) + 1;
+                    let product = (u128::from(self.0.value()) * u128::from(other.0.value())) % width;
+
+                    Self(<$t>::new(product as _))
+                }
+            }
+
+            impl Zero for ArbitraryIntId<$t> {
+                fn zero() -> Self {
+                    Self(<$t>::new(0))
+                }
+
+                fn is_zero(&self) -> bool {
+                    self.0.value() == 0
+                }
+            }
+
+            impl One for ArbitraryIntId<$t> {
+                fn one() -> Self {
+                    Self(<$t>::new(1))
+                }
+            }
+
+            impl Bounded for ArbitraryIntId<$t> {
+                fn min_value() -> Self {
+                    Self(<$t>::new(0))
+                }
+
+                fn max_value() -> Self {
+                    Self(<$t>::MAX)
+                }
+            }
+
+            impl SaturatingAdd for ArbitraryIntId<$t> {
+                fn saturating_add(&self, other: &Self) -> Self {
+                    let max = u64::from(<$t>::MAX.value());
+                    let sum = u64::from(self.0.value()) + u64::from(other.0.value());
+
+                    Self(<$t>::new(sum.min(max) as _))
+                }
+            }
+
+            impl SaturatingSub for ArbitraryIntId<$t> {
+                fn saturating_sub(&self, other: &Self) -> Self {
+                    let a = u64::from(self.0.value());
+                    let b = u64::from(other.0.value());
+
+                    Self(<$t>::new(a.saturating_sub(b) as _))
+                }
+            }
+
+            impl WrappingAdd for ArbitraryIntId<$t> {
+                fn wrapping_add(&self, other: &Self) -> Self {
+                    let width = u64::from(<$t>::MAX.value()) + 1;
+                    let sum = (u64::from(self.0.value()) + u64::from(other.0.value())) % width;
+
+                    Self(<$t>::new(sum as _))
+                }
+            }
+        )*
+    };
+}
+
+impl_num_traits_for_arbitrary_int!(u4, u7, u24, u48);
+
+#[cfg(test)]
+mod tests {
+    use arbitrary_int::u24;
+
+    use super::*;
+    use crate::Factory;
+
+    #[test]
+    fn test_factory_arbitrary_int_id() {
+        let mut factory = Factory::<ArbitraryIntId<u24>>::builder().build();
+
+        assert_eq!(factory.next().unwrap(), ArbitraryIntId(u24::new(0)));
+        assert_eq!(factory.next().unwrap(), ArbitraryIntId(u24::new(1)));
+
+        factory.remove(ArbitraryIntId(u24::new(0)));
+        assert_eq!(factory.next().unwrap(), ArbitraryIntId(u24::new(0)));
+        assert_eq!(factory.next().unwrap(), ArbitraryIntId(u24::new(2)));
+    }
+
+    #[test]
+    fn test_factory_arbitrary_int_id_max_value() {
+        let mut factory = Factory::<ArbitraryIntId<u24>>::builder()
+            .initial_value(ArbitraryIntId(u24::MAX))
+            .build();
+
+        assert_eq!(factory.next().unwrap(), ArbitraryIntId(u24::MAX));
+        assert!(factory.next().is_err());
+    }
+}
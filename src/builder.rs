@@ -1,18 +1,21 @@
 use crate::{aictable::Aictable, factory::Factory};
 
 /// A builder to build a new [`Factory`](crate::Factory).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FactoryBuilder<T: Aictable> {
     initial_value: T,
     looping: bool,
-    rewind: bool,
+    max: Option<T>,
+    step: T,
 }
 
 impl<T: Aictable> Default for FactoryBuilder<T> {
     fn default() -> Self {
         Self {
-            initial_value: T::INITIAL,
+            initial_value: T::initial(),
             looping: false,
-            rewind: false,
+            max: None,
+            step: T::one(),
         }
     }
 }
@@ -24,15 +27,17 @@ impl<T: Aictable> FactoryBuilder<T> {
 
     /// Sets the initial value for the IDs in the [`Factory`](crate::Factory).
     ///
-    /// - Default: The [`Aictable::INITIAL`] value of the type.
-    /// Usually is the minimum value of the type.
+    /// - Default: [`Aictable::initial`](crate::Aictable::initial), which is zero.
     pub fn initial_value(mut self, initial_value: T) -> Self {
         self.initial_value = initial_value;
         self
     }
 
-    /// If true, the [`Factory`](crate::Factory) will loop back to the initial value
-    /// after reaching the maximum value.
+    /// Since [`Factory::next`](crate::Factory::next) always returns the lowest free
+    /// ID in `[initial_value, max]`, reaching the upper bound with nothing left to
+    /// reuse always means the same thing: the factory is out of IDs. This only
+    /// selects which error variant is returned in that case: [`Error::OutOfSpace`](crate::Error::OutOfSpace)
+    /// if true, [`Error::MaxReached`](crate::Error::MaxReached) if false.
     ///
     /// - Default: false
     pub fn looping(mut self, looping: bool) -> Self {
@@ -40,16 +45,25 @@ impl<T: Aictable> FactoryBuilder<T> {
         self
     }
 
-    /// If true, the [`Factory`](crate::Factory) will rewind to the position of
-    /// the latest removed ID when generating the next ID.
+    /// Sets the upper bound for the IDs in the [`Factory`](crate::Factory), confining
+    /// generation to the closed interval `[initial_value, max]`.
     ///
-    /// - Default: false
-    pub fn rewind(mut self, rewind: bool) -> Self {
-        self.rewind = rewind;
+    /// - Default: `None`, meaning the maximum value of the type is used.
+    pub fn max_value(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the step size used when advancing to the next ID in the
+    /// [`Factory`](crate::Factory).
+    ///
+    /// - Default: one.
+    pub fn step(mut self, step: T) -> Self {
+        self.step = step;
         self
     }
 
     pub fn build(self) -> Factory<T> {
-        Factory::new(self.initial_value, self.looping, self.rewind)
+        Factory::new(self.initial_value, self.looping, self.max, self.step)
     }
 }
@@ -7,6 +7,13 @@ use crate::aictable::Aictable;
 pub enum Error<T: Aictable> {
     /// This error occurs when an ID already exists in the [`Factory`](crate::Factory).
     AlreadyExist(T),
+    /// This error occurs when a [`FactoryState`](crate::FactoryState) being restored via
+    /// [`Factory::from_state`](crate::Factory::from_state) is inconsistent, e.g. one of
+    /// its free intervals lies outside `[initial_value, max]` or overlaps another.
+    InvalidState,
+    /// This error occurs when [`Factory::next_block`](crate::Factory::next_block) is
+    /// called with a `count` of zero, which would reserve an empty block.
+    InvalidBlockSize,
     /// This error occurs when the maximum value for the type has been reached.
     MaxReached,
     /// This error occurs when there are no more IDs left to generate.
@@ -17,6 +24,8 @@ impl<T: Aictable> fmt::Display for Error<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::AlreadyExist(id) => write!(f, "Id {:?} already exists", id),
+            Self::InvalidState => write!(f, "Invalid factory state"),
+            Self::InvalidBlockSize => write!(f, "Block size must be non-zero"),
             Self::MaxReached => write!(f, "Maximum reached"),
             Self::OutOfSpace => write!(f, "No more id left"),
         }
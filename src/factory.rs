@@ -1,32 +1,47 @@
-use std::collections::HashSet;
+use std::collections::BTreeMap;
 
-use crate::{aictable::Aictable, builder::FactoryBuilder, error::Error};
+use crate::{aictable::Aictable, builder::FactoryBuilder, error::Error, state::FactoryState};
 
 /// A factory for generating unique IDs of a specific type.
+///
+/// Internally, the set of IDs that have not yet been generated (or that have
+/// been [`remove`](Factory::remove)d) is tracked as a map of contiguous free
+/// intervals, keyed by each interval's start and storing its inclusive end.
+/// This keeps memory proportional to the number of free intervals rather than
+/// to the number of IDs ever generated, and makes allocation, removal and
+/// take-up all `O(log k)` in the number of intervals `k`.
+///
+/// With a `step` greater than one, the IDs skipped over between generated IDs
+/// are not kept in the free intervals either, so they are treated like
+/// generated IDs: [`take_up`](Factory::take_up) reports them as already
+/// existing, and [`remove`](Factory::remove) frees them and returns true.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Factory<T: Aictable> {
     initial_value: T,
     looping: bool,
-    rewind: bool,
+    max: Option<T>,
+    step: T,
 
-    cursor: T,
-    set: HashSet<T>,
+    free: BTreeMap<T, T>,
 }
 
 impl<T: Aictable> Factory<T> {
-    /// Creates a new `Factory` with the specified initial value, looping behavior, and rewind behavior.
+    /// Creates a new `Factory` with the specified initial value, looping behavior,
+    /// upper bound, and step size.
     ///
     /// It's recommended to use a [`FactoryBuilder`](crate::FactoryBuilder) to build a `Factory`.
     /// See the document of `FactoryBuilder` for details.
-    pub fn new(initial_value: T, looping: bool, rewind: bool) -> Self {
-        let cursor = initial_value.clone();
+    pub fn new(initial_value: T, looping: bool, max: Option<T>, step: T) -> Self {
+        let mut free = BTreeMap::new();
+        free.insert(initial_value.clone(), Self::upper_bound(&max));
 
         Self {
             initial_value,
             looping,
-            rewind,
+            max,
+            step,
 
-            cursor,
-            set: HashSet::new(),
+            free,
         }
     }
 
@@ -35,66 +50,218 @@ impl<T: Aictable> Factory<T> {
         FactoryBuilder::default()
     }
 
+    /// Returns a snapshot of this factory's internal state, which can be persisted
+    /// (e.g. via the `serde` feature) and later restored with
+    /// [`Factory::from_state`](Factory::from_state).
+    pub fn state(&self) -> FactoryState<T> {
+        FactoryState {
+            initial_value: self.initial_value.clone(),
+            looping: self.looping,
+            max: self.max.clone(),
+            step: self.step.clone(),
+            free: self.free.clone(),
+        }
+    }
+
+    /// Restores a `Factory` from a previously captured [`FactoryState`].
+    ///
+    /// # Errors
+    ///
+    /// - If `step` is zero, it returns an [`Error::InvalidState`].
+    /// - If the state's free intervals are inconsistent (out of `[initial_value, max]`,
+    ///   unsorted, overlapping, or abutting without being merged into a single interval),
+    ///   it returns an [`Error::InvalidState`].
+    pub fn from_state(state: FactoryState<T>) -> Result<Self, Error<T>> {
+        if state.step.is_zero() {
+            return Err(Error::InvalidState);
+        }
+
+        let upper = Self::upper_bound(&state.max);
+
+        let mut prev_end: Option<T> = None;
+
+        for (start, end) in state.free.iter() {
+            if start > end || *start < state.initial_value || *end > upper {
+                return Err(Error::InvalidState);
+            }
+
+            if let Some(prev_end) = &prev_end {
+                if *start <= prev_end.succ() {
+                    return Err(Error::InvalidState);
+                }
+            }
+
+            prev_end = Some(end.clone());
+        }
+
+        Ok(Self {
+            initial_value: state.initial_value,
+            looping: state.looping,
+            max: state.max,
+            step: state.step,
+            free: state.free,
+        })
+    }
+
     /// Generates and returns the next unique ID.
     ///
+    /// IDs are generated within the closed interval `[initial_value, max]`, where
+    /// `max` defaults to the maximum value of the type unless overridden via
+    /// [`FactoryBuilder::max_value`](crate::FactoryBuilder::max_value). Allocation
+    /// always returns the lowest free ID, so an ID freed via
+    /// [`remove`](Factory::remove) is reused on the next call.
+    ///
     /// # Errors
     ///
-    /// - If no more IDs can be generated, it returns an [`Error::OutOfSpace`].
-    /// - If `Factory::looping` is false and the current ID is already
-    /// the maximum value of the type, it returns an [`Error::MaxReached`].
+    /// - If `Factory::looping` is true and every ID in the range has been
+    ///   generated, it returns an [`Error::OutOfSpace`].
+    /// - If `Factory::looping` is false and every ID in the range has been
+    ///   generated, it returns an [`Error::MaxReached`].
     pub fn next(&mut self) -> Result<T, Error<T>> {
-        let start = self.cursor.clone();
+        let (start, end) = match self.free.iter().next() {
+            Some((start, end)) => (start.clone(), end.clone()),
+            None => {
+                return Err(if self.looping {
+                    Error::OutOfSpace
+                } else {
+                    Error::MaxReached
+                })
+            }
+        };
 
-        while self.set.contains(&self.cursor) {
-            if self.looping {
-                self.cursor = self.cursor.wrapping_next();
+        self.free.remove(&start);
 
-                if self.cursor == start {
-                    return Err(Error::OutOfSpace);
-                }
-            } else {
-                self.cursor = self.cursor.saturating_next();
+        let advanced = start.saturating_add_step(&self.step);
 
-                if self.cursor.is_max_reached() {
-                    return Err(Error::MaxReached);
-                }
+        if advanced > start && advanced <= end {
+            self.free.insert(advanced, end);
+        }
+
+        Ok(start)
+    }
+
+    /// Generates and returns the first ID of a contiguous block of `count` unique
+    /// IDs, reserving all of them at once.
+    ///
+    /// This scans the free intervals for the first one spanning at least `count`
+    /// IDs, so unlike repeated calls to [`next`](Factory::next) the returned block
+    /// is guaranteed to be contiguous even once [`remove`](Factory::remove)/
+    /// [`take_up`](Factory::take_up) have punched holes in the range.
+    ///
+    /// # Errors
+    ///
+    /// - If `count` is zero, it returns an [`Error::InvalidBlockSize`].
+    /// - If `Factory::looping` is true and no free interval spans `count` IDs, it
+    ///   returns an [`Error::OutOfSpace`].
+    /// - If `Factory::looping` is false and no free interval spans `count` IDs, it
+    ///   returns an [`Error::MaxReached`].
+    pub fn next_block(&mut self, count: T) -> Result<T, Error<T>> {
+        if count.is_zero() {
+            return Err(Error::InvalidBlockSize);
+        }
+
+        let found = self
+            .free
+            .iter()
+            .find(|(start, end)| Self::interval_len(start, end) >= count)
+            .map(|(start, end)| (start.clone(), end.clone()));
+
+        let (start, end) = match found {
+            Some(interval) => interval,
+            None => {
+                return Err(if self.looping {
+                    Error::OutOfSpace
+                } else {
+                    Error::MaxReached
+                })
             }
+        };
+
+        self.free.remove(&start);
+
+        // The last ID in the reserved block. `interval_len(start, end) >= count`
+        // guarantees this stays within `[start, end]`, so this can never
+        // saturate past the block it's meant to bound.
+        let block_end = start.saturating_add_step(&count.pred());
+
+        if block_end < end {
+            self.free.insert(block_end.succ(), end);
         }
 
-        self.set.insert(self.cursor.clone());
+        Ok(start)
+    }
 
-        let next = self.cursor.clone();
+    /// Returns the number of IDs spanned by the closed interval `[start, end]`.
+    fn interval_len(start: &T, end: &T) -> T {
+        end.saturating_sub_value(start).succ()
+    }
 
-        if self.looping {
-            self.cursor = self.cursor.wrapping_next();
-        } else {
-            self.cursor = self.cursor.saturating_next();
+    /// Finds the free interval containing `id`, if any.
+    fn free_interval_containing(&self, id: &T) -> Option<(T, T)> {
+        match self.free.range(..=id.clone()).next_back() {
+            Some((start, end)) if end >= id => Some((start.clone(), end.clone())),
+            _ => None,
         }
+    }
 
-        Ok(next)
+    fn upper_bound(max: &Option<T>) -> T {
+        max.clone().unwrap_or_else(T::max_value)
     }
 
     /// Removes the specified ID from the set of generated IDs,
     /// so that it can be reused.
     ///
-    /// Returns whether the ID was present in the set.
+    /// Returns whether the ID was present in the set of generated IDs. IDs outside
+    /// `[initial_value, max]` were never generated by this factory and are never
+    /// considered present, even if they happen to collide with a free interval's
+    /// bookkeeping.
     pub fn remove(&mut self, id: T) -> bool {
-        if self.set.remove(&id) {
-            if self.rewind {
-                self.cursor = id;
+        if id < self.initial_value || id > Self::upper_bound(&self.max) {
+            return false;
+        }
+
+        if self.free_interval_containing(&id).is_some() {
+            return false;
+        }
+
+        let left = self
+            .free
+            .range(..id.clone())
+            .next_back()
+            .map(|(start, end)| (start.clone(), end.clone()));
+        let right = self
+            .free
+            .range(id.clone()..)
+            .next()
+            .map(|(start, end)| (start.clone(), end.clone()));
+
+        let mut start = id.clone();
+        let mut end = id;
+
+        if let Some((left_start, left_end)) = left {
+            if left_end.succ() == start {
+                self.free.remove(&left_start);
+                start = left_start;
             }
+        }
 
-            return true;
+        if let Some((right_start, right_end)) = right {
+            if end.succ() == right_start {
+                self.free.remove(&right_start);
+                end = right_end;
+            }
         }
 
-        false
+        self.free.insert(start, end);
+
+        true
     }
 
     /// Resets the factory to its initial state.
     pub fn reset(&mut self) {
-        self.cursor = self.initial_value.clone();
-
-        self.set.clear();
+        self.free.clear();
+        self.free
+            .insert(self.initial_value.clone(), Self::upper_bound(&self.max));
     }
 
     /// Manually marks the specified ID as used.
@@ -103,11 +270,20 @@ impl<T: Aictable> Factory<T> {
     ///
     /// - If the ID is already taken, it returns an [`Error::AlreadyExist<T>`].
     pub fn take_up(&mut self, id: T) -> Result<(), Error<T>> {
-        if self.set.contains(&id) {
-            return Err(Error::AlreadyExist(id));
+        let (start, end) = match self.free_interval_containing(&id) {
+            Some(interval) => interval,
+            None => return Err(Error::AlreadyExist(id)),
+        };
+
+        self.free.remove(&start);
+
+        if start < id {
+            self.free.insert(start, id.pred());
         }
 
-        self.set.insert(id);
+        if id < end {
+            self.free.insert(id.succ(), end);
+        }
 
         Ok(())
     }
@@ -124,13 +300,13 @@ mod tests {
         assert_eq!(factory.next().unwrap(), 0);
         assert_eq!(factory.next().unwrap(), 1);
         factory.remove(1);
-        assert_eq!(factory.next().unwrap(), 2);
-        assert!(factory.take_up(2).is_err());
+        assert_eq!(factory.next().unwrap(), 1);
+        assert!(factory.take_up(1).is_err());
         assert!(factory.take_up(3).is_ok());
         assert!(factory.take_up(3).is_err());
         factory.remove(3);
         assert!(factory.take_up(3).is_ok());
-        assert_eq!(factory.next().unwrap(), 4);
+        assert_eq!(factory.next().unwrap(), 2);
         factory.reset();
         assert_eq!(factory.next().unwrap(), 0);
 
@@ -151,9 +327,9 @@ mod tests {
             .build();
 
         assert_eq!(factory.next().unwrap(), u32::MAX);
-        assert_eq!(factory.next().unwrap(), u32::MIN);
+        assert!(factory.next().is_err());
 
-        factory = Factory::<u32>::builder().rewind(true).build();
+        factory = Factory::<u32>::builder().build();
 
         assert_eq!(factory.next().unwrap(), 0);
         assert_eq!(factory.next().unwrap(), 1);
@@ -165,6 +341,161 @@ mod tests {
         assert_eq!(factory.next().unwrap(), 3);
         factory.remove(0);
         factory.remove(1);
+        assert_eq!(factory.next().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_factory_max_value() {
+        let mut factory = Factory::<u32>::builder()
+            .initial_value(100)
+            .max_value(102)
+            .build();
+
+        assert_eq!(factory.next().unwrap(), 100);
+        assert_eq!(factory.next().unwrap(), 101);
+        assert_eq!(factory.next().unwrap(), 102);
+        assert!(factory.next().is_err());
+
+        factory = Factory::<u32>::builder()
+            .initial_value(100)
+            .max_value(102)
+            .looping(true)
+            .build();
+
+        assert_eq!(factory.next().unwrap(), 100);
+        assert_eq!(factory.next().unwrap(), 101);
+        assert_eq!(factory.next().unwrap(), 102);
+        assert!(factory.next().is_err());
+    }
+
+    #[test]
+    fn test_factory_remove_out_of_range() {
+        let mut factory = Factory::<u32>::builder().max_value(2).build();
+
+        assert_eq!(factory.next().unwrap(), 0);
         assert_eq!(factory.next().unwrap(), 1);
+        assert_eq!(factory.next().unwrap(), 2);
+
+        assert!(!factory.remove(100));
+        assert!(factory.next().is_err());
+    }
+
+    #[test]
+    fn test_factory_step() {
+        let mut factory = Factory::<u32>::builder().step(4).build();
+
+        assert_eq!(factory.next().unwrap(), 0);
+        assert_eq!(factory.next().unwrap(), 4);
+        assert_eq!(factory.next().unwrap(), 8);
+        factory.remove(4);
+        assert_eq!(factory.next().unwrap(), 4);
+        assert_eq!(factory.next().unwrap(), 12);
+    }
+
+    #[test]
+    fn test_factory_next_block() {
+        let mut factory = Factory::<u32>::builder().build();
+
+        assert_eq!(factory.next_block(4).unwrap(), 0);
+        assert_eq!(factory.next().unwrap(), 4);
+
+        factory = Factory::<u32>::builder()
+            .initial_value(100)
+            .max_value(102)
+            .build();
+
+        assert!(factory.next_block(4).is_err());
+        assert_eq!(factory.next_block(3).unwrap(), 100);
+        assert!(factory.next().is_err());
+    }
+
+    #[test]
+    fn test_factory_next_block_reserves_to_upper_bound() {
+        let mut factory = Factory::<u8>::builder().initial_value(253).build();
+
+        assert_eq!(factory.next_block(3).unwrap(), 253);
+        assert!(factory.next().is_err());
+    }
+
+    #[test]
+    fn test_factory_next_block_zero() {
+        let mut factory = Factory::<u32>::builder().build();
+
+        assert!(matches!(
+            factory.next_block(0),
+            Err(Error::InvalidBlockSize)
+        ));
+        assert_eq!(factory.next().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_factory_state() {
+        let mut factory = Factory::<u32>::builder().max_value(10).build();
+
+        assert_eq!(factory.next().unwrap(), 0);
+        assert_eq!(factory.next().unwrap(), 1);
+        factory.remove(0);
+
+        let restored = Factory::from_state(factory.state()).unwrap();
+
+        let mut factory = restored;
+
+        assert_eq!(factory.next().unwrap(), 0);
+        assert_eq!(factory.next().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_factory_from_state_invalid() {
+        let mut free = BTreeMap::new();
+        free.insert(5u32, 2u32);
+
+        let state = FactoryState {
+            initial_value: 0,
+            looping: false,
+            max: Some(10),
+            step: 1,
+            free,
+        };
+
+        assert!(Factory::from_state(state).is_err());
+    }
+
+    #[test]
+    fn test_factory_from_state_abutting_intervals() {
+        let mut free = BTreeMap::new();
+        free.insert(1u32, 5u32);
+        free.insert(6u32, 10u32);
+
+        let state = FactoryState {
+            initial_value: 0,
+            looping: false,
+            max: Some(10),
+            step: 1,
+            free,
+        };
+
+        assert!(matches!(
+            Factory::from_state(state),
+            Err(Error::InvalidState)
+        ));
+    }
+
+    #[test]
+    fn test_factory_from_state_zero_step() {
+        let mut free = BTreeMap::new();
+        free.insert(0u32, 10u32);
+
+        let state = FactoryState {
+            initial_value: 0,
+            looping: false,
+            max: Some(10),
+            step: 0,
+            free,
+        };
+
+        assert!(matches!(
+            Factory::from_state(state),
+            Err(Error::InvalidState)
+        ));
     }
 }
@@ -4,6 +4,14 @@
 //!
 //! It provides built-in support for integer types (`i8`, `i16`, `i32`, `i64`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize`). However, you can add support for your own types by implementing the [`Aictable`] trait.
 //!
+//! Enabling the `arbitrary-int` feature adds [`ArbitraryIntId`], a wrapper that lets the
+//! fixed-width integer types (`u4`, `u7`, `u24`, `u48`, ...) from the
+//! [`arbitrary-int`](https://docs.rs/arbitrary-int) crate be used as ID types.
+//!
+//! Enabling the `serde` feature adds `Serialize`/`Deserialize` support for [`Factory`] and
+//! [`FactoryBuilder`], and lets a [`Factory`]'s state be snapshotted and restored across
+//! restarts via [`Factory::state`](Factory::state)/[`Factory::from_state`](Factory::from_state).
+//!
 //! ## Example
 //!
 //! ```rust
@@ -12,17 +20,14 @@
 //! // Creates a new Factory for u32 IDs.
 //! let mut factory = Factory::<u32>::builder()
 //!     // Sets the initial value for the IDs.
-//!     // For built-in types, the default value is the minimum value.
+//!     // For built-in types, the default value is zero.
 //!     // .initial_value(1)
 //!
-//!     // Whether to loop back to the initial value after reaching the maximum value.
+//!     // Whether exhausting the range returns Error::OutOfSpace (true) or
+//!     // Error::MaxReached (false).
 //!     // Default is false.
 //!     // .looping(true)
 //!
-//!     // Whether to rewind to the position of the latest removed ID when generating the next ID.
-//!     // Default is false.
-//!     // .rewind(true)
-//!
 //!     .build();
 //!
 //! // Generates some IDs.
@@ -34,17 +39,23 @@
 //!
 //! // Manually marks an ID as used.
 //! assert!(factory.take_up(2).is_ok());
-//! // Since 2 was taken up, the next available ID is 3.
-//! // However, if rewind is set to true, the next ID is 0.
-//! assert_eq!(factory.next().unwrap(), 3);
+//! // The lowest free ID is always generated next, so the removed 0 is reused
+//! // before the still-unclaimed 3.
+//! assert_eq!(factory.next().unwrap(), 0);
 //! ```
 
 mod aictable;
+#[cfg(feature = "arbitrary-int")]
+mod arbitrary_int_support;
 mod builder;
 mod error;
 mod factory;
+mod state;
 
 pub use aictable::Aictable;
+#[cfg(feature = "arbitrary-int")]
+pub use arbitrary_int_support::ArbitraryIntId;
 pub use builder::FactoryBuilder;
 pub use error::Error;
 pub use factory::Factory;
+pub use state::FactoryState;
@@ -0,0 +1,16 @@
+use std::collections::BTreeMap;
+
+use crate::aictable::Aictable;
+
+/// A snapshot of a [`Factory`](crate::Factory)'s internal state, suitable for
+/// persisting across restarts and restoring later via
+/// [`Factory::from_state`](crate::Factory::from_state).
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FactoryState<T: Aictable> {
+    pub(crate) initial_value: T,
+    pub(crate) looping: bool,
+    pub(crate) max: Option<T>,
+    pub(crate) step: T,
+    pub(crate) free: BTreeMap<T, T>,
+}